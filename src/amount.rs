@@ -0,0 +1,202 @@
+//! Fixed-point amount types shared across operations.
+//!
+//! Stellar represents amounts internally as signed 64-bit integers scaled by
+//! a fixed 7 decimal places ("stroops"): `1 unit = 10_000_000 stroops`.
+//! [`Stroops`] keeps that raw, XDR-level representation opaque and
+//! range-checked so invalid amounts cannot be constructed, mirroring the
+//! pattern used for `Amount`.
+
+use crate::error::{Error, Result};
+use crate::xdr;
+use std::convert::TryFrom;
+
+/// Stellar's fixed-point scale: 7 decimal digits.
+const STROOPS_SCALE: i64 = 10_000_000;
+const STROOPS_DECIMALS: usize = 7;
+
+/// An amount of stroops, the smallest indivisible unit in Stellar, held in
+/// the range `0..=i64::MAX`.
+///
+/// The constructors on this type are the only way to obtain a `Stroops`, so
+/// a value in hand is always known to be non-negative.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Stroops(i64);
+
+impl Stroops {
+    /// Creates a new `Stroops`, checking that `amount` is in `0..=i64::MAX`.
+    pub fn new(amount: i64) -> Result<Stroops> {
+        if amount < 0 {
+            return Err(Error::InvalidStroopsAmount);
+        }
+        Ok(Stroops(amount))
+    }
+
+    /// The largest representable amount, `i64::MAX` stroops.
+    pub fn max() -> Stroops {
+        Stroops(i64::MAX)
+    }
+
+    /// Returns the raw stroops value.
+    pub fn to_i64(&self) -> i64 {
+        self.0
+    }
+
+    /// Parses a human-readable decimal amount (e.g. `"2.5"`) into its
+    /// stroops representation, scaling by `10_000_000`.
+    ///
+    /// Rejects more than 7 fractional digits, amounts that overflow
+    /// `i64::MAX`, and anything that isn't a plain optionally-signed decimal
+    /// number (leading/trailing zeros are accepted and normalized away).
+    pub fn from_decimal(decimal: &str) -> Result<Stroops> {
+        let decimal = decimal.trim();
+        let (sign, unsigned) = match decimal.strip_prefix('-') {
+            Some(rest) => (-1i64, rest),
+            None => (1i64, decimal.strip_prefix('+').unwrap_or(decimal)),
+        };
+        if unsigned.is_empty() {
+            return Err(Error::InvalidStroopsAmount);
+        }
+
+        let mut parts = unsigned.splitn(2, '.');
+        let integer_part = parts.next().unwrap_or("");
+        let fractional_part = parts.next().unwrap_or("");
+
+        if (integer_part.is_empty() && fractional_part.is_empty())
+            || !integer_part.chars().all(|c| c.is_ascii_digit())
+            || !fractional_part.chars().all(|c| c.is_ascii_digit())
+            || fractional_part.len() > STROOPS_DECIMALS
+        {
+            return Err(Error::InvalidStroopsAmount);
+        }
+
+        let integer_value: i64 = if integer_part.is_empty() {
+            0
+        } else {
+            integer_part
+                .parse()
+                .map_err(|_| Error::InvalidStroopsAmount)?
+        };
+        let mut fractional_value: i64 = if fractional_part.is_empty() {
+            0
+        } else {
+            fractional_part
+                .parse()
+                .map_err(|_| Error::InvalidStroopsAmount)?
+        };
+        fractional_value *= 10i64.pow((STROOPS_DECIMALS - fractional_part.len()) as u32);
+
+        let scaled_integer = integer_value
+            .checked_mul(STROOPS_SCALE)
+            .ok_or(Error::InvalidStroopsAmount)?;
+        let magnitude = scaled_integer
+            .checked_add(fractional_value)
+            .ok_or(Error::InvalidStroopsAmount)?;
+
+        Stroops::new(sign * magnitude)
+    }
+
+    /// Formats this amount back into its human-readable decimal string,
+    /// the inverse of [`Stroops::from_decimal`].
+    pub fn to_decimal_string(&self) -> String {
+        let integer_part = self.0 / STROOPS_SCALE;
+        let fractional_part = self.0 % STROOPS_SCALE;
+        if fractional_part == 0 {
+            return integer_part.to_string();
+        }
+        format!("{}.{:07}", integer_part, fractional_part)
+            .trim_end_matches('0')
+            .trim_end_matches('.')
+            .to_string()
+    }
+
+    /// Returns the xdr `Int64` representation of this amount.
+    pub fn to_xdr_int64(&self) -> Result<xdr::Int64> {
+        Ok(xdr::Int64::new(self.0))
+    }
+}
+
+impl TryFrom<i64> for Stroops {
+    type Error = Error;
+
+    fn try_from(amount: i64) -> Result<Stroops> {
+        Stroops::new(amount)
+    }
+}
+
+impl TryFrom<&str> for Stroops {
+    type Error = Error;
+
+    fn try_from(decimal: &str) -> Result<Stroops> {
+        Stroops::from_decimal(decimal)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_decimal_round_trip() {
+        let stroops = Stroops::from_decimal("2.5").unwrap();
+        assert_eq!(25_000_000, stroops.to_i64());
+        assert_eq!("2.5", stroops.to_decimal_string());
+    }
+
+    #[test]
+    fn test_from_decimal_handles_leading_and_trailing_zeros() {
+        let stroops = Stroops::from_decimal("007.500000").unwrap();
+        assert_eq!(75_000_000, stroops.to_i64());
+        assert_eq!("7.5", stroops.to_decimal_string());
+    }
+
+    #[test]
+    fn test_from_decimal_integer_has_no_decimal_point() {
+        let stroops = Stroops::from_decimal("42").unwrap();
+        assert_eq!(420_000_000, stroops.to_i64());
+        assert_eq!("42", stroops.to_decimal_string());
+    }
+
+    #[test]
+    fn test_from_decimal_rejects_negative() {
+        assert!(Stroops::from_decimal("-1").is_err());
+    }
+
+    #[test]
+    fn test_from_decimal_rejects_too_many_fractional_digits() {
+        assert!(Stroops::from_decimal("1.12345678").is_err());
+    }
+
+    #[test]
+    fn test_from_decimal_rejects_garbage() {
+        assert!(Stroops::from_decimal("not a number").is_err());
+        assert!(Stroops::from_decimal("").is_err());
+    }
+
+    #[test]
+    fn test_from_decimal_boundary_at_i64_max() {
+        // i64::MAX stroops is "922337203685.4775807"; one more stroop overflows.
+        assert_eq!(
+            i64::MAX,
+            Stroops::from_decimal("922337203685.4775807")
+                .unwrap()
+                .to_i64()
+        );
+        assert!(Stroops::from_decimal("922337203685.4775808").is_err());
+    }
+
+    #[test]
+    fn test_new_rejects_negative() {
+        assert!(matches!(Stroops::new(-1), Err(Error::InvalidStroopsAmount)));
+    }
+
+    #[test]
+    fn test_new_accepts_zero_and_positive() {
+        assert_eq!(0, Stroops::new(0).unwrap().to_i64());
+        assert_eq!(42, Stroops::new(42).unwrap().to_i64());
+    }
+
+    #[test]
+    fn test_max_is_i64_max() {
+        assert_eq!(i64::MAX, Stroops::max().to_i64());
+    }
+}
@@ -0,0 +1,143 @@
+//! SEP-0005 mnemonic-based key derivation.
+//!
+//! Most Stellar wallets hand users a BIP-39 recovery phrase rather than a
+//! raw secret seed. This module derives the [`KeyPair`] for such a phrase by
+//! running the standard BIP-39 seed derivation followed by SLIP-0010 ed25519
+//! derivation along the SEP-0005 path `m/44'/148'/{account}'`.
+
+use crate::crypto::KeyPair;
+use crate::error::{Error, Result};
+use hmac::{Hmac, Mac};
+use sha2::Sha512;
+use tiny_bip39::{Language, Mnemonic, Seed};
+use zeroize::Zeroize;
+
+type HmacSha512 = Hmac<Sha512>;
+
+const ED25519_SEED_KEY: &[u8] = b"ed25519 seed";
+const HARDENED_OFFSET: u32 = 0x8000_0000;
+
+/// SEP-0005 purpose and coin type for Stellar: `44'/148'`.
+const STELLAR_PURPOSE: u32 = 44;
+const STELLAR_COIN_TYPE: u32 = 148;
+
+/// A SLIP-0010 extended ed25519 private key: the 32-byte key plus its
+/// 32-byte chain code. Zeroized on drop since it is derived from secret
+/// material.
+struct ExtendedKey {
+    key: [u8; 32],
+    chain_code: [u8; 32],
+}
+
+impl Drop for ExtendedKey {
+    fn drop(&mut self) {
+        self.key.zeroize();
+        self.chain_code.zeroize();
+    }
+}
+
+fn hmac_sha512(key: &[u8], data: &[u8]) -> [u8; 64] {
+    let mut mac = HmacSha512::new_from_slice(key).expect("HMAC-SHA512 accepts any key length");
+    mac.update(data);
+    let mut out = [0u8; 64];
+    out.copy_from_slice(&mac.finalize().into_bytes());
+    out
+}
+
+fn split_node(mut i: [u8; 64]) -> ExtendedKey {
+    let mut key = [0u8; 32];
+    let mut chain_code = [0u8; 32];
+    key.copy_from_slice(&i[..32]);
+    chain_code.copy_from_slice(&i[32..]);
+    i.zeroize();
+    ExtendedKey { key, chain_code }
+}
+
+fn master_node(seed: &[u8]) -> ExtendedKey {
+    split_node(hmac_sha512(ED25519_SEED_KEY, seed))
+}
+
+/// Derives the hardened child at `index`. SLIP-0010 only defines hardened
+/// derivation for ed25519, so `index` is always treated as hardened.
+fn hardened_child_node(parent: &ExtendedKey, index: u32) -> Result<ExtendedKey> {
+    if index & HARDENED_OFFSET != 0 {
+        return Err(Error::InvalidOperation(
+            "stellar key derivation indexes must be given unhardened".to_string(),
+        ));
+    }
+    let hardened_index = HARDENED_OFFSET | index;
+    let mut data = Vec::with_capacity(1 + 32 + 4);
+    data.push(0u8);
+    data.extend_from_slice(&parent.key);
+    data.extend_from_slice(&hardened_index.to_be_bytes());
+    let node = split_node(hmac_sha512(&parent.chain_code, &data));
+    data.zeroize();
+    Ok(node)
+}
+
+impl KeyPair {
+    /// Derives a `KeyPair` from a BIP-39 `phrase` following
+    /// [SEP-0005](https://stellar.org/protocol/sep-5), along the path
+    /// `m/44'/148'/{account_index}'`.
+    ///
+    /// `passphrase` is the optional BIP-39 passphrase (the empty string if
+    /// none was set). Returns `Error::InvalidOperation` if `phrase` is not a
+    /// valid English BIP-39 mnemonic, e.g. its checksum does not match.
+    ///
+    /// The seed and SLIP-0010 intermediate buffers this function owns are
+    /// zeroized before returning. The `tiny_bip39::Mnemonic`/`Seed` values
+    /// produced while parsing `phrase` are dropped as early as possible,
+    /// but that crate does not zeroize its own entropy/seed buffers, so a
+    /// residual copy of the secret material may still remain in memory.
+    pub fn from_mnemonic(phrase: &str, passphrase: &str, account_index: u32) -> Result<KeyPair> {
+        let mnemonic = Mnemonic::from_phrase(phrase, Language::English)
+            .map_err(|_| Error::InvalidOperation("invalid BIP-39 mnemonic".to_string()))?;
+        let mut seed = Seed::new(&mnemonic, passphrase).as_bytes().to_vec();
+        drop(mnemonic);
+
+        let mut node = master_node(&seed);
+        seed.zeroize();
+        for index in [STELLAR_PURPOSE, STELLAR_COIN_TYPE, account_index] {
+            node = hardened_child_node(&node, index)?;
+        }
+
+        KeyPair::from_secret_seed(&node.key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Published SEP-0005 test vector, account 0 of
+    /// `m/44'/148'/0'` for this mnemonic with no passphrase.
+    const SEP0005_MNEMONIC: &str =
+        "illness spike retreat truth genius clock brain pass fit cave bargain toe";
+    const SEP0005_ACCOUNT_0_PUBLIC_KEY: &str =
+        "GDRXE2BQUC3AZNPVFSCEZ76NJ3WWL25FYFK6RGZGIEKWE4SOOHSUJUJ6";
+
+    #[test]
+    fn test_from_mnemonic_sep0005_vector() {
+        let keypair = KeyPair::from_mnemonic(SEP0005_MNEMONIC, "", 0).unwrap();
+        assert_eq!(
+            SEP0005_ACCOUNT_0_PUBLIC_KEY,
+            keypair.public_key().to_string()
+        );
+    }
+
+    #[test]
+    fn test_from_mnemonic_rejects_invalid_checksum() {
+        // Same words as the valid vector above, but the last word is
+        // swapped for another valid BIP-39 word, which changes the
+        // encoded entropy and so (almost certainly) the checksum.
+        let invalid = "illness spike retreat truth genius clock brain pass fit cave bargain zebra";
+        assert!(KeyPair::from_mnemonic(invalid, "", 0).is_err());
+    }
+
+    #[test]
+    fn test_hardened_child_node_rejects_already_hardened_index() {
+        let node = master_node(&[0u8; 64]);
+        let err = hardened_child_node(&node, HARDENED_OFFSET).unwrap_err();
+        assert!(matches!(err, Error::InvalidOperation(_)));
+    }
+}
@@ -6,17 +6,97 @@ use crate::operations::Operation;
 use crate::xdr;
 use std::convert::TryInto;
 
+/// The pool fee for constant-product liquidity pools, in basis points.
+/// CAP-0038 only defines this one fee tier.
+pub const LIQUIDITY_POOL_FEE_V18: i32 = 30;
+
+/// The asset side of a `ChangeTrust` operation: either a classic [`Asset`]
+/// trustline, or a trustline to the pool shares of a constant-product
+/// liquidity pool (CAP-0038).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChangeTrustAsset {
+    Asset(Asset),
+    ConstantProductPool { asset_a: Asset, asset_b: Asset },
+}
+
+impl ChangeTrustAsset {
+    /// Creates a trustline asset for the constant-product liquidity pool
+    /// between `asset_a` and `asset_b`, at the fixed 30 bps pool fee.
+    ///
+    /// CAP-0038 requires pool assets to be distinct and given in Stellar's
+    /// canonical asset ordering (`asset_a < asset_b`); this is also how
+    /// stellar-core derives the pool id, so an out-of-order or duplicate
+    /// pair is rejected here rather than failing at submission time.
+    pub fn new_constant_product_pool(asset_a: Asset, asset_b: Asset) -> Result<ChangeTrustAsset> {
+        if asset_a >= asset_b {
+            return Err(Error::InvalidOperation(
+                "liquidity pool assets must be distinct and in canonical order".to_string(),
+            ));
+        }
+        Ok(ChangeTrustAsset::ConstantProductPool { asset_a, asset_b })
+    }
+
+    /// Returns the xdr representation of this trustline asset.
+    pub fn to_xdr(&self) -> Result<xdr::ChangeTrustAsset> {
+        match self {
+            ChangeTrustAsset::Asset(asset) => Ok(xdr::ChangeTrustAsset::Asset(asset.to_xdr()?)),
+            ChangeTrustAsset::ConstantProductPool { asset_a, asset_b } => {
+                let params = xdr::LiquidityPoolConstantProductParameters {
+                    asset_a: asset_a.to_xdr()?,
+                    asset_b: asset_b.to_xdr()?,
+                    fee: LIQUIDITY_POOL_FEE_V18,
+                };
+                Ok(xdr::ChangeTrustAsset::PoolShare(
+                    xdr::LiquidityPoolParameters::ConstantProduct(params),
+                ))
+            }
+        }
+    }
+
+    /// Creates from the xdr representation of a trustline asset.
+    ///
+    /// Rejects a pool fee other than [`LIQUIDITY_POOL_FEE_V18`]: this type
+    /// only retains `asset_a`/`asset_b`, so silently accepting a different
+    /// fee would make `to_xdr` re-emit the wrong one on round trip.
+    pub fn from_xdr(x: &xdr::ChangeTrustAsset) -> Result<ChangeTrustAsset> {
+        match x {
+            xdr::ChangeTrustAsset::Asset(asset) => {
+                Ok(ChangeTrustAsset::Asset(Asset::from_xdr(asset)?))
+            }
+            xdr::ChangeTrustAsset::PoolShare(xdr::LiquidityPoolParameters::ConstantProduct(
+                params,
+            )) => {
+                if params.fee != LIQUIDITY_POOL_FEE_V18 {
+                    return Err(Error::InvalidOperation(
+                        "unsupported liquidity pool fee".to_string(),
+                    ));
+                }
+                Ok(ChangeTrustAsset::ConstantProductPool {
+                    asset_a: Asset::from_xdr(&params.asset_a)?,
+                    asset_b: Asset::from_xdr(&params.asset_b)?,
+                })
+            }
+        }
+    }
+}
+
+impl From<Asset> for ChangeTrustAsset {
+    fn from(asset: Asset) -> ChangeTrustAsset {
+        ChangeTrustAsset::Asset(asset)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ChangeTrustOperation {
     source_account: Option<MuxedAccount>,
-    asset: Asset,
+    asset: ChangeTrustAsset,
     limit: Option<Stroops>,
 }
 
 #[derive(Debug, Default)]
 pub struct ChangeTrustOperationBuilder {
     source_account: Option<MuxedAccount>,
-    asset: Option<Asset>,
+    asset: Option<ChangeTrustAsset>,
     limit: Option<Stroops>,
 }
 
@@ -32,12 +112,12 @@ impl ChangeTrustOperation {
     }
 
     /// Retrieves the operation asset.
-    pub fn asset(&self) -> &Asset {
+    pub fn asset(&self) -> &ChangeTrustAsset {
         &self.asset
     }
 
     /// Retrieves a mutable reference the operation asset.
-    pub fn asset_mut(&mut self) -> &mut Asset {
+    pub fn asset_mut(&mut self) -> &mut ChangeTrustAsset {
         &mut self.asset
     }
 
@@ -67,12 +147,10 @@ impl ChangeTrustOperation {
         source_account: Option<MuxedAccount>,
         x: &xdr::ChangeTrustOp,
     ) -> Result<ChangeTrustOperation> {
-        let asset = Asset::from_xdr(&x.line)?;
-        // Don't check if limit is positive because the library sure
-        // has no control over the xdr.
+        let asset = ChangeTrustAsset::from_xdr(&x.line)?;
         let limit = match &x.limit.value {
             0 => None,
-            n => Some(Stroops::new(*n)),
+            n => Some(Stroops::new(*n)?),
         };
         Ok(ChangeTrustOperation {
             source_account,
@@ -95,8 +173,11 @@ impl ChangeTrustOperationBuilder {
         self
     }
 
-    pub fn with_asset(mut self, asset: Asset) -> ChangeTrustOperationBuilder {
-        self.asset = Some(asset);
+    pub fn with_asset<A: Into<ChangeTrustAsset>>(
+        mut self,
+        asset: A,
+    ) -> ChangeTrustOperationBuilder {
+        self.asset = Some(asset.into());
         self
     }
 
@@ -116,14 +197,6 @@ impl ChangeTrustOperationBuilder {
             .asset
             .ok_or_else(|| Error::InvalidOperation("missing change trust asset".to_string()))?;
 
-        if let Some(limit) = &self.limit {
-            if limit.to_i64() < 0 {
-                return Err(Error::InvalidOperation(
-                    "change trust limit must be positive".to_string(),
-                ));
-            }
-        }
-
         Ok(Operation::ChangeTrust(ChangeTrustOperation {
             source_account: self.source_account,
             asset,
@@ -141,8 +214,11 @@ mod tests {
     use crate::operations::tests::*;
     use crate::operations::Operation;
     use crate::transaction::{Transaction, TransactionEnvelope, MIN_BASE_FEE};
+    use crate::xdr;
     use crate::xdr::{XDRDeserialize, XDRSerialize};
 
+    use super::{ChangeTrustAsset, ChangeTrustOperation, LIQUIDITY_POOL_FEE_V18};
+
     #[test]
     fn test_change_trust() {
         let kp = keypair0();
@@ -220,4 +296,106 @@ mod tests {
         let back = TransactionEnvelope::from_xdr_base64(&xdr).unwrap();
         assert_eq!(envelope, back);
     }
+
+    #[test]
+    fn test_new_constant_product_pool_rejects_out_of_order_assets() {
+        let kp1 = keypair1();
+        let asset_a = Asset::native();
+        let asset_b = Asset::new_credit("FOOBAR", kp1.public_key().clone()).unwrap();
+
+        // `asset_a` must sort before `asset_b` in Stellar's canonical asset
+        // ordering; passing them reversed must be rejected.
+        assert!(ChangeTrustAsset::new_constant_product_pool(asset_b, asset_a).is_err());
+    }
+
+    #[test]
+    fn test_new_constant_product_pool_rejects_out_of_order_same_type_assets() {
+        let kp1 = keypair1();
+        // Two AlphaNum4 assets with the same issuer: only the asset code
+        // differs, so this exercises same-type ordering rather than the
+        // type-discriminant ordering a derived `Ord` gets right for free.
+        let lower = Asset::new_credit("AAAA", kp1.public_key().clone()).unwrap();
+        let higher = Asset::new_credit("ZZZZ", kp1.public_key().clone()).unwrap();
+
+        assert!(ChangeTrustAsset::new_constant_product_pool(lower.clone(), higher.clone()).is_ok());
+        assert!(ChangeTrustAsset::new_constant_product_pool(higher, lower).is_err());
+    }
+
+    #[test]
+    fn test_new_constant_product_pool_rejects_identical_assets() {
+        let kp1 = keypair1();
+        let asset = Asset::new_credit("FOOBAR", kp1.public_key().clone()).unwrap();
+
+        assert!(ChangeTrustAsset::new_constant_product_pool(asset.clone(), asset).is_err());
+    }
+
+    #[test]
+    fn test_change_trust_asset_pool_share_xdr_round_trip() {
+        let kp1 = keypair1();
+        // Same-type (AlphaNum4/AlphaNum4) pair, as above: a silently
+        // inverted `Ord` on `Asset` would either fail to construct this
+        // pool or, if it somehow did round-trip, would do so with
+        // `asset_a`/`asset_b` swapped, which the explicit field checks
+        // below would catch.
+        let lower = Asset::new_credit("AAAA", kp1.public_key().clone()).unwrap();
+        let higher = Asset::new_credit("ZZZZ", kp1.public_key().clone()).unwrap();
+        let pool_asset =
+            ChangeTrustAsset::new_constant_product_pool(lower.clone(), higher.clone()).unwrap();
+
+        // No pinned base64 XDR vector here (unlike the signed-tx tests
+        // above): this checkout has no buildable crate to generate one
+        // against, so the round trip is exercised at the to_xdr/from_xdr
+        // level instead.
+        let xdr = pool_asset.to_xdr().unwrap();
+        let back = ChangeTrustAsset::from_xdr(&xdr).unwrap();
+        assert_eq!(pool_asset, back);
+        match back {
+            ChangeTrustAsset::ConstantProductPool { asset_a, asset_b } => {
+                assert_eq!(lower, asset_a);
+                assert_eq!(higher, asset_b);
+            }
+            ChangeTrustAsset::Asset(_) => panic!("expected a ConstantProductPool"),
+        }
+    }
+
+    #[test]
+    fn test_change_trust_asset_from_xdr_rejects_unsupported_pool_fee() {
+        let kp1 = keypair1();
+        let asset_a = Asset::native();
+        let asset_b = Asset::new_credit("FOOBAR", kp1.public_key().clone()).unwrap();
+
+        let params = xdr::LiquidityPoolConstantProductParameters {
+            asset_a: asset_a.to_xdr().unwrap(),
+            asset_b: asset_b.to_xdr().unwrap(),
+            fee: LIQUIDITY_POOL_FEE_V18 + 1,
+        };
+        let bad_fee_xdr =
+            xdr::ChangeTrustAsset::PoolShare(xdr::LiquidityPoolParameters::ConstantProduct(params));
+        assert!(ChangeTrustAsset::from_xdr(&bad_fee_xdr).is_err());
+    }
+
+    #[test]
+    fn test_change_trust_with_pool_share_asset() {
+        let kp1 = keypair1();
+        let asset_a = Asset::native();
+        let asset_b = Asset::new_credit("FOOBAR", kp1.public_key().clone()).unwrap();
+        let pool_asset = ChangeTrustAsset::new_constant_product_pool(asset_a, asset_b).unwrap();
+
+        // `with_asset` takes `Into<ChangeTrustAsset>`, so a pool-share asset
+        // can be passed directly, same as a classic `Asset`.
+        let op = Operation::new_change_trust()
+            .with_asset(pool_asset.clone())
+            .build()
+            .unwrap();
+        let body = match &op {
+            Operation::ChangeTrust(change_trust) => change_trust.to_xdr_operation_body().unwrap(),
+            _ => panic!("expected a ChangeTrust operation"),
+        };
+        let inner = match &body {
+            xdr::OperationBody::ChangeTrust(inner) => inner,
+            _ => panic!("expected a ChangeTrust operation body"),
+        };
+        let back = ChangeTrustOperation::from_xdr_operation_body(None, inner).unwrap();
+        assert_eq!(&pool_asset, back.asset());
+    }
 }